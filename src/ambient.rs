@@ -0,0 +1,91 @@
+use openrgb::data::Color;
+use scrap::{Capturer, Display};
+use std::error::Error;
+use std::io::ErrorKind::WouldBlock;
+use std::thread;
+use std::time::Duration;
+
+pub struct AmbientCapture {
+    capturer: Capturer,
+    region_size: u32,
+}
+
+impl AmbientCapture {
+    pub fn new(region_size: u32) -> Result<Self, Box<dyn Error>> {
+        let display = Display::primary()?;
+        let capturer = Capturer::new(display)?;
+        Ok(Self {
+            capturer,
+            region_size,
+        })
+    }
+
+    // Scaled to the capture's physical pixel dimensions, not logical ones,
+    // so HiDPI displays sample the right region.
+    pub fn sample(&mut self, leds: &[Option<(f32, f32)>]) -> Vec<Color> {
+        let width = self.capturer.width();
+        let height = self.capturer.height();
+
+        let frame = loop {
+            match self.capturer.frame() {
+                Ok(frame) => break frame,
+                Err(ref e) if e.kind() == WouldBlock => {
+                    thread::sleep(Duration::from_millis(1));
+                    continue;
+                }
+                Err(e) => {
+                    eprintln!("Error capturing frame: {e}");
+                    return vec![Color::new(0, 0, 0); leds.len()];
+                }
+            }
+        };
+
+        let stride = frame.len() / height;
+        let half = (self.region_size / 2) as usize;
+
+        leds.iter()
+            .map(|led| match led {
+                Some((x, y)) => {
+                    let cx = (*x * width as f32) as usize;
+                    let cy = (*y * height as f32) as usize;
+                    sample_region(&frame, width, height, stride, cx, cy, half)
+                }
+                None => Color::new(0, 0, 0),
+            })
+            .collect()
+    }
+}
+
+fn sample_region(
+    frame: &[u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+    cx: usize,
+    cy: usize,
+    half: usize,
+) -> Color {
+    let x0 = cx.saturating_sub(half);
+    let y0 = cy.saturating_sub(half);
+    let x1 = (cx + half).min(width.saturating_sub(1));
+    let y1 = (cy + half).min(height.saturating_sub(1));
+
+    let (mut r, mut g, mut b, mut count) = (0u32, 0u32, 0u32, 0u32);
+    for py in y0..=y1 {
+        let row = &frame[py * stride..py * stride + width * 4];
+        for px in x0..=x1 {
+            let pixel = &row[px * 4..px * 4 + 4];
+            // scrap delivers frames as BGRA regardless of platform.
+            b += pixel[0] as u32;
+            g += pixel[1] as u32;
+            r += pixel[2] as u32;
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        Color::new(0, 0, 0)
+    } else {
+        Color::new((r / count) as u8, (g / count) as u8, (b / count) as u8)
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::VecDeque;
+use std::ops::Range;
+
+// Sub-bass band, in Hz, that beats are detected against.
+pub const SUB_BASS_RANGE: Range<usize> = 20..120;
+
+// ~2.15s of history at main's 50ms frame rate.
+const HISTORY_LEN: usize = 43;
+
+const MIN_SENSITIVITY: f32 = 1.3;
+const MAX_SENSITIVITY: f32 = 1.5;
+
+pub struct BeatDetector {
+    history: VecDeque<f32>,
+    frames_since_beat: usize,
+    refractory_frames: usize,
+    frame_seconds: f32,
+    last_beat_interval_frames: Option<usize>,
+}
+
+impl BeatDetector {
+    pub fn new(frame_seconds: f32, refractory_frames: usize) -> Self {
+        Self {
+            history: VecDeque::with_capacity(HISTORY_LEN),
+            frames_since_beat: refractory_frames,
+            refractory_frames,
+            frame_seconds,
+            last_beat_interval_frames: None,
+        }
+    }
+
+    pub fn update(&mut self, sub_bass_energy: f32) -> bool {
+        self.frames_since_beat += 1;
+
+        let mean = average(&self.history);
+        let variance = if self.history.is_empty() {
+            0.0
+        } else {
+            self.history.iter().map(|e| (e - mean).powi(2)).sum::<f32>() / self.history.len() as f32
+        };
+        let sensitivity = (MIN_SENSITIVITY + variance.sqrt()).clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+
+        let is_beat = !self.history.is_empty()
+            && self.frames_since_beat >= self.refractory_frames
+            && sub_bass_energy > mean * sensitivity;
+
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(sub_bass_energy);
+
+        if is_beat {
+            self.last_beat_interval_frames = Some(self.frames_since_beat);
+            self.frames_since_beat = 0;
+        }
+
+        is_beat
+    }
+
+    pub fn estimated_bpm(&self) -> Option<f32> {
+        self.last_beat_interval_frames
+            .filter(|&frames| frames > 0)
+            .map(|frames| 60.0 / (frames as f32 * self.frame_seconds))
+    }
+}
+
+fn average(history: &VecDeque<f32>) -> f32 {
+    if history.is_empty() {
+        0.0
+    } else {
+        history.iter().sum::<f32>() / history.len() as f32
+    }
+}
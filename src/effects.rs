@@ -0,0 +1,121 @@
+use crate::gradient::{Gradient, Stop};
+use openrgb::data::Color;
+use rand::Rng;
+
+pub fn fade_to_black_by(leds: &mut [Color], amount: f32) {
+    let keep = 1.0 - amount.clamp(0.0, 1.0);
+    for led in leds.iter_mut() {
+        *led = Color::new(
+            (led.r as f32 * keep) as u8,
+            (led.g as f32 * keep) as u8,
+            (led.b as f32 * keep) as u8,
+        );
+    }
+}
+
+pub struct SweepingDot {
+    position: f32,
+    color: Color,
+    leds: Vec<Color>,
+}
+
+impl SweepingDot {
+    pub fn new(color: Color, led_count: usize) -> Self {
+        Self {
+            position: 0.0,
+            color,
+            leds: vec![Color::new(0, 0, 0); led_count],
+        }
+    }
+
+    pub fn render(&mut self, beats_per_frame: f32, fade: f32) -> &[Color] {
+        if self.leds.is_empty() {
+            return &self.leds;
+        }
+        fade_to_black_by(&mut self.leds, fade);
+        self.position =
+            (self.position + beats_per_frame * self.leds.len() as f32) % self.leds.len() as f32;
+        self.leds[self.position as usize] = self.color;
+        &self.leds
+    }
+}
+
+pub struct Rain {
+    color: Color,
+    threshold: f32,
+    leds: Vec<Color>,
+}
+
+impl Rain {
+    pub fn new(color: Color, threshold: f32, led_count: usize) -> Self {
+        Self {
+            color,
+            threshold,
+            leds: vec![Color::new(0, 0, 0); led_count],
+        }
+    }
+
+    pub fn render(&mut self, new_energy: f32, fade: f32) -> &[Color] {
+        fade_to_black_by(&mut self.leds, fade);
+        if new_energy > self.threshold && !self.leds.is_empty() {
+            let index = rand::thread_rng().gen_range(0..self.leds.len());
+            self.leds[index] = self.color;
+        }
+        &self.leds
+    }
+}
+
+const FIRE_SEED_INDEX: usize = 0;
+const MAX_ENERGY_PROPAGATION: f32 = 0.4;
+const COOLDOWN_MULTIPLY: f32 = 0.99995;
+const COOLDOWN_SUBTRACT: f32 = 0.011;
+const FIRE_EXPONENT: f32 = 1.5;
+
+pub struct Fire {
+    energy: Vec<f32>,
+    palette: Gradient,
+}
+
+impl Fire {
+    pub fn new(len: usize) -> Self {
+        Self {
+            energy: vec![0.0; len],
+            palette: Gradient::Stops(vec![
+                Stop::new(0.0, Color::new(0, 0, 0)),
+                Stop::new(0.4, Color::new(255, 0, 0)),
+                Stop::new(0.7, Color::new(255, 140, 0)),
+                Stop::new(0.9, Color::new(255, 255, 0)),
+                Stop::new(1.0, Color::new(255, 255, 255)),
+            ]),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.energy.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.energy.is_empty()
+    }
+
+    pub fn render(&mut self, leds: &mut [Color], new_energy: f32) {
+        if self.energy.is_empty() {
+            return;
+        }
+
+        let seed = FIRE_SEED_INDEX.min(self.energy.len() - 1);
+        self.energy[seed] += rand::thread_rng().gen::<f32>() * new_energy;
+
+        for i in (1..self.energy.len()).rev() {
+            self.energy[i] += self.energy[i - 1] * MAX_ENERGY_PROPAGATION;
+        }
+
+        for e in self.energy.iter_mut() {
+            *e = (*e * COOLDOWN_MULTIPLY - COOLDOWN_SUBTRACT).max(0.0);
+        }
+
+        for (led, &e) in leds.iter_mut().zip(self.energy.iter()) {
+            *led = self.palette.sample(e.clamp(0.0, 1.0).powf(FIRE_EXPONENT));
+        }
+    }
+}
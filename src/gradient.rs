@@ -0,0 +1,104 @@
+use openrgb::data::Color;
+use std::ops::Range;
+
+pub struct Stop {
+    pub t: f32,
+    pub color: Color,
+}
+
+impl Stop {
+    pub fn new(t: f32, color: Color) -> Self {
+        Self { t, color }
+    }
+}
+
+pub enum Gradient {
+    Stops(Vec<Stop>),
+    Hsv { hue_range: Range<f32>, saturation: f32 },
+}
+
+impl Gradient {
+    pub fn classic() -> Self {
+        Gradient::Stops(vec![
+            Stop::new(0.0, Color::new(0, 0, 0)),
+            Stop::new(0.25, Color::new(0, 0, 255)),
+            Stop::new(0.5, Color::new(255, 0, 255)),
+            Stop::new(0.75, Color::new(255, 0, 0)),
+            Stop::new(1.0, Color::new(255, 255, 255)),
+        ])
+    }
+
+    pub fn hsv(hue_range: Range<f32>, saturation: f32) -> Self {
+        Gradient::Hsv {
+            hue_range,
+            saturation,
+        }
+    }
+
+    pub fn sample(&self, t: f32) -> Color {
+        self.sample_with_hue(t, 0.0)
+    }
+
+    pub fn sample_with_hue(&self, t: f32, freq_t: f32) -> Color {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Gradient::Stops(stops) => sample_stops(stops, t),
+            Gradient::Hsv {
+                hue_range,
+                saturation,
+            } => {
+                let freq_t = freq_t.clamp(0.0, 1.0);
+                let hue = hue_range.start + freq_t * (hue_range.end - hue_range.start);
+                hsv_to_rgb(hue, *saturation, t)
+            }
+        }
+    }
+}
+
+fn sample_stops(stops: &[Stop], t: f32) -> Color {
+    let Some(first) = stops.first() else {
+        return Color::new(0, 0, 0);
+    };
+    if t <= first.t {
+        return Color::new(first.color.r, first.color.g, first.color.b);
+    }
+
+    for pair in stops.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if t <= b.t {
+            let local_t = (t - a.t) / (b.t - a.t).max(f32::EPSILON);
+            return Color::new(
+                lerp(a.color.r, b.color.r, local_t),
+                lerp(a.color.g, b.color.g, local_t),
+                lerp(a.color.b, b.color.b, local_t),
+            );
+        }
+    }
+
+    let last = stops.last().unwrap();
+    Color::new(last.color.r, last.color.g, last.color.b)
+}
+
+fn lerp(a: u8, b: u8, t: f32) -> u8 {
+    (a as f32 + (b as f32 - a as f32) * t).round() as u8
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> Color {
+    let hue = hue.rem_euclid(360.0);
+    let c = value * saturation;
+    let x = c * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = value - c;
+    let (r, g, b) = match hue as u32 {
+        0..=59 => (c, x, 0.0),
+        60..=119 => (x, c, 0.0),
+        120..=179 => (0.0, c, x),
+        180..=239 => (0.0, x, c),
+        240..=299 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    Color::new(
+        ((r + m) * 255.0).round() as u8,
+        ((g + m) * 255.0).round() as u8,
+        ((b + m) * 255.0).round() as u8,
+    )
+}
@@ -1,11 +1,144 @@
+use ambient::AmbientCapture;
+use beat::BeatDetector;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use effects::{Fire, Rain, SweepingDot};
+use gradient::Gradient;
 use openrgb::{data::Color, OpenRGB};
 use rustfft::{num_complex::Complex, FftPlanner};
 use serde_json::Value;
+use sink::{LedSink, OpenRgbSink, WledProtocol, WledSink};
 use std::error::Error;
 use std::io::BufReader;
 use std::ops::Range;
 use std::sync::{Arc, Mutex};
+use transform::{ColorOp, ColorTransform};
+
+mod ambient;
+mod beat;
+mod effects;
+mod gradient;
+mod sink;
+mod transform;
+
+fn color_transform() -> ColorTransform {
+    ColorTransform::new(vec![
+        ColorOp::WhiteBalance(1.0, 1.0, 1.0),
+        ColorOp::Gamma(2.2),
+        ColorOp::Brightness(1.0),
+    ])
+}
+
+const BEAT_REFRACTORY_FRAMES: usize = 6;
+const BEAT_ENERGY_BOOST: f32 = 1.5;
+
+const MAGNITUDE_SCALE: f32 = 4.0;
+
+enum GradientConfig {
+    Classic,
+    Hsv { hue_range: (f32, f32), saturation: f32 },
+}
+
+const GRADIENT: GradientConfig = GradientConfig::Classic;
+
+fn build_gradient(config: &GradientConfig) -> Gradient {
+    match *config {
+        GradientConfig::Classic => Gradient::classic(),
+        GradientConfig::Hsv {
+            hue_range,
+            saturation,
+        } => Gradient::hsv(hue_range.0..hue_range.1, saturation),
+    }
+}
+
+enum OutputConfig {
+    OpenRgb { host: &'static str, port: u16 },
+    Wled { host: &'static str },
+}
+
+const OUTPUT: OutputConfig = OutputConfig::OpenRgb {
+    host: "localhost",
+    port: 6742,
+};
+
+enum DrivingMode {
+    Audio,
+    Ambient { region_size: u32, frame_millis: u64 },
+}
+
+const MODE: DrivingMode = DrivingMode::Audio;
+
+const SMOOTHING_DECAY: f32 = 0.7;
+
+enum Effect {
+    Spectrum,
+    Dot {
+        color: (u8, u8, u8),
+        beats_per_second: f32,
+        fade: f32,
+    },
+    Rain {
+        color: (u8, u8, u8),
+        threshold: f32,
+        fade: f32,
+    },
+    Fire,
+}
+
+const EFFECT: Effect = Effect::Spectrum;
+
+enum EffectState {
+    Dot { dot: SweepingDot, beats_per_second: f32, fade: f32 },
+    Rain { rain: Rain, fade: f32 },
+    Fire(Fire),
+}
+
+impl EffectState {
+    fn new(effect: &Effect, led_count: usize) -> Option<Self> {
+        match *effect {
+            Effect::Spectrum => None,
+            Effect::Dot {
+                color,
+                beats_per_second,
+                fade,
+            } => Some(EffectState::Dot {
+                dot: SweepingDot::new(Color::new(color.0, color.1, color.2), led_count),
+                beats_per_second,
+                fade,
+            }),
+            Effect::Rain {
+                color,
+                threshold,
+                fade,
+            } => Some(EffectState::Rain {
+                rain: Rain::new(Color::new(color.0, color.1, color.2), threshold, led_count),
+                fade,
+            }),
+            Effect::Fire => Some(EffectState::Fire(Fire::new(led_count))),
+        }
+    }
+
+    fn render(&mut self, new_energy: f32, frame_seconds: f32, estimated_bpm: Option<f32>) -> Vec<Color> {
+        match self {
+            EffectState::Dot {
+                dot,
+                beats_per_second,
+                fade,
+            } => {
+                let beats_per_frame = match estimated_bpm {
+                    Some(bpm) => (bpm / 60.0) * frame_seconds,
+                    None => new_energy * *beats_per_second * frame_seconds,
+                };
+                dot.render(beats_per_frame, *fade).to_vec()
+            }
+            EffectState::Rain { rain, fade } => rain.render(new_energy, *fade).to_vec(),
+            EffectState::Fire(fire) => {
+                let mut colors = vec![Color::new(0, 0, 0); fire.len()];
+                fire.render(&mut colors, new_energy);
+                colors
+            }
+        }
+    }
+}
 
 fn average(slice: &[f32]) -> f32 {
     let sum: f32 = slice.iter().sum();
@@ -17,6 +150,17 @@ fn average(slice: &[f32]) -> f32 {
     }
 }
 
+fn hann_window(data: &[f32]) -> Vec<f32> {
+    let len = data.len();
+    data.iter()
+        .enumerate()
+        .map(|(n, &sample)| {
+            let w = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * n as f32 / (len - 1) as f32).cos());
+            sample * w
+        })
+        .collect()
+}
+
 fn calculate_fft(data: &[f32], len: usize) -> Vec<Complex<f32>> {
     let fft = FftPlanner::new().plan_fft_forward(len);
     let mut buffer: Vec<Complex<f32>> = data
@@ -104,19 +248,19 @@ fn paint(
     leds: &Vec<Option<(f32, f32)>>,
     bins: &Vec<(Range<usize>, f32)>,
     sample_rate: f32,
+    gradient: &Gradient,
 ) -> Vec<Color> {
+    let max_freq = sample_rate / 2.0;
     leds.iter()
         .map(|led| match led {
             Some((x, y)) => {
                 let bin = bins
                     .iter()
-                    .find(|(range, _)| range.contains(&((*x * sample_rate / 2.0) as usize)))
+                    .find(|(range, _)| range.contains(&((*x * max_freq) as usize)))
                     .unwrap();
-                if bin.1 > 1.0 {
-                    Color::new(255, 0, 0)
-                } else {
-                    Color::new(0, 0, 0)
-                }
+                let magnitude_t = bin.1 / MAGNITUDE_SCALE;
+                let freq_t = (bin.0.start + bin.0.end) as f32 / 2.0 / max_freq;
+                gradient.sample_with_hue(magnitude_t, freq_t)
             }
             None => Color::new(0, 0, 0),
         })
@@ -145,8 +289,29 @@ async fn main() -> Result<(), Box<dyn Error>> {
         })
         .collect();
 
-    let client = OpenRGB::connect_to(("localhost", 6742)).await?;
     let controller_id = 0;
+    let sink: Box<dyn LedSink> = match OUTPUT {
+        OutputConfig::OpenRgb { host, port } => {
+            let client = OpenRGB::connect_to((host, port)).await?;
+            Box::new(OpenRgbSink::new(client, controller_id))
+        }
+        OutputConfig::Wled { host } => Box::new(WledSink::new(host, WledProtocol::Drgb, 2)?),
+    };
+    let color_transform = color_transform();
+
+    if let DrivingMode::Ambient {
+        region_size,
+        frame_millis,
+    } = MODE
+    {
+        let mut capture = AmbientCapture::new(region_size)?;
+        loop {
+            let mut colors = capture.sample(&leds);
+            color_transform.apply(&mut colors);
+            sink.send(&colors).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(frame_millis)).await;
+        }
+    }
 
     let device = cpal::default_host()
         .input_devices()?
@@ -181,22 +346,54 @@ async fn main() -> Result<(), Box<dyn Error>> {
 
     stream.play()?;
 
+    let gradient = build_gradient(&GRADIENT);
+    let mut effect_state = EffectState::new(&EFFECT, leds.len());
+    let frame_seconds = window_size_millis / 1000.0;
+    let mut beat_detector = BeatDetector::new(frame_seconds, BEAT_REFRACTORY_FRAMES);
     let window_proc = window.clone();
+    let bin_count = 10;
+    let mut prev_magnitudes = vec![0.0f32; bin_count];
     tokio::spawn(async move {
         loop {
             if let Some(colors) = {
                 let mut window = window_proc.lock().unwrap();
                 if window.len() >= window_size_samples {
                     let slice = &window[window.len() - window_size_samples..];
-                    let fft = calculate_fft(slice, window_size_samples);
-                    let bins = Binning::Linear(10).bin(&fft, sample_rate);
+                    let windowed = hann_window(slice);
+                    let fft = calculate_fft(&windowed, window_size_samples);
+                    let bins: Vec<_> = Binning::Linear(bin_count)
+                        .bin(&fft, sample_rate)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (range, magnitude))| {
+                            let shown = magnitude.max(prev_magnitudes[i] * SMOOTHING_DECAY);
+                            prev_magnitudes[i] = shown;
+                            (range, shown)
+                        })
+                        .collect();
                     window.clear();
-                    Some(paint(&leds, &bins, sample_rate))
+
+                    let sub_bass_energy =
+                        Binning::Ranges(vec![beat::SUB_BASS_RANGE]).bin(&fft, sample_rate)[0].1;
+                    let is_beat = beat_detector.update(sub_bass_energy);
+                    let driven_energy = if is_beat {
+                        sub_bass_energy * BEAT_ENERGY_BOOST
+                    } else {
+                        sub_bass_energy
+                    };
+                    let estimated_bpm = beat_detector.estimated_bpm();
+
+                    let mut colors = match &mut effect_state {
+                        Some(state) => state.render(driven_energy, frame_seconds, estimated_bpm),
+                        None => paint(&leds, &bins, sample_rate, &gradient),
+                    };
+                    color_transform.apply(&mut colors);
+                    Some(colors)
                 } else {
                     None
                 }
             } {
-                client.update_leds(controller_id, colors).await.unwrap();
+                sink.send(&colors).await.unwrap();
             }
             tokio::time::sleep(std::time::Duration::from_millis(window_size_millis as u64)).await;
         }
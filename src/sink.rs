@@ -0,0 +1,108 @@
+use async_trait::async_trait;
+use openrgb::{data::Color, OpenRGB};
+use std::error::Error;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+
+#[async_trait]
+pub trait LedSink {
+    async fn send(&self, colors: &[Color]) -> Result<(), Box<dyn Error>>;
+}
+
+pub struct OpenRgbSink {
+    client: OpenRGB<tokio::net::TcpStream>,
+    controller_id: u32,
+}
+
+impl OpenRgbSink {
+    pub fn new(client: OpenRGB<tokio::net::TcpStream>, controller_id: u32) -> Self {
+        Self {
+            client,
+            controller_id,
+        }
+    }
+}
+
+#[async_trait]
+impl LedSink for OpenRgbSink {
+    async fn send(&self, colors: &[Color]) -> Result<(), Box<dyn Error>> {
+        self.client
+            .update_leds(self.controller_id, colors.to_vec())
+            .await?;
+        Ok(())
+    }
+}
+
+// See https://kno.wled.ge/interfaces/udp-realtime/ for the wire layout.
+pub enum WledProtocol {
+    Warls,
+    Drgb,
+    Dnrgb,
+}
+
+const WLED_REALTIME_PORT: u16 = 21324;
+const DNRGB_MAX_LEDS_PER_PACKET: usize = 490;
+
+pub struct WledSink {
+    socket: UdpSocket,
+    addr: SocketAddr,
+    protocol: WledProtocol,
+    timeout_secs: u8,
+}
+
+impl WledSink {
+    pub fn new(
+        host: &str,
+        protocol: WledProtocol,
+        timeout_secs: u8,
+    ) -> Result<Self, Box<dyn Error>> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        let addr = (host, WLED_REALTIME_PORT)
+            .to_socket_addrs()?
+            .next()
+            .ok_or("could not resolve WLED host")?;
+        Ok(Self {
+            socket,
+            addr,
+            protocol,
+            timeout_secs,
+        })
+    }
+}
+
+#[async_trait]
+impl LedSink for WledSink {
+    async fn send(&self, colors: &[Color]) -> Result<(), Box<dyn Error>> {
+        match self.protocol {
+            WledProtocol::Warls => {
+                if colors.len() > 256 {
+                    return Err("WARLS can only address 256 LEDs; use DNRGB or DRGB instead"
+                        .into());
+                }
+                let mut packet = vec![1, self.timeout_secs];
+                for (i, color) in colors.iter().enumerate() {
+                    packet.extend_from_slice(&[i as u8, color.r, color.g, color.b]);
+                }
+                self.socket.send_to(&packet, self.addr)?;
+            }
+            WledProtocol::Drgb => {
+                let mut packet = vec![2, self.timeout_secs];
+                for color in colors {
+                    packet.extend_from_slice(&[color.r, color.g, color.b]);
+                }
+                self.socket.send_to(&packet, self.addr)?;
+            }
+            WledProtocol::Dnrgb => {
+                for (chunk_index, chunk) in colors.chunks(DNRGB_MAX_LEDS_PER_PACKET).enumerate() {
+                    let start = (chunk_index * DNRGB_MAX_LEDS_PER_PACKET) as u16;
+                    let mut packet = vec![4, self.timeout_secs];
+                    packet.extend_from_slice(&start.to_be_bytes());
+                    for color in chunk {
+                        packet.extend_from_slice(&[color.r, color.g, color.b]);
+                    }
+                    self.socket.send_to(&packet, self.addr)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use openrgb::data::Color;
+
+pub enum ColorOp {
+    Brightness(f32),
+    WhiteBalance(f32, f32, f32),
+    // out = round(255 * (in/255).powf(gamma))
+    Gamma(f32),
+}
+
+pub struct ColorTransform {
+    ops: Vec<ColorOp>,
+}
+
+impl ColorTransform {
+    pub fn new(ops: Vec<ColorOp>) -> Self {
+        Self { ops }
+    }
+
+    pub fn apply(&self, colors: &mut [Color]) {
+        for color in colors.iter_mut() {
+            for op in &self.ops {
+                *color = apply_op(op, *color);
+            }
+        }
+    }
+}
+
+fn apply_op(op: &ColorOp, color: Color) -> Color {
+    match op {
+        ColorOp::Brightness(scalar) => Color::new(
+            scale_channel(color.r, *scalar),
+            scale_channel(color.g, *scalar),
+            scale_channel(color.b, *scalar),
+        ),
+        ColorOp::WhiteBalance(r_gain, g_gain, b_gain) => Color::new(
+            scale_channel(color.r, *r_gain),
+            scale_channel(color.g, *g_gain),
+            scale_channel(color.b, *b_gain),
+        ),
+        ColorOp::Gamma(gamma) => Color::new(
+            gamma_channel(color.r, *gamma),
+            gamma_channel(color.g, *gamma),
+            gamma_channel(color.b, *gamma),
+        ),
+    }
+}
+
+fn scale_channel(channel: u8, scalar: f32) -> u8 {
+    (channel as f32 * scalar).round().clamp(0.0, 255.0) as u8
+}
+
+fn gamma_channel(channel: u8, gamma: f32) -> u8 {
+    (255.0 * (channel as f32 / 255.0).powf(gamma))
+        .round()
+        .clamp(0.0, 255.0) as u8
+}